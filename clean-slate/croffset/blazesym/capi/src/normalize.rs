@@ -1,17 +1,26 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::CStr;
 use std::ffi::CString;
 use std::ffi::OsString;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::fs;
+use std::io;
 use std::mem::size_of;
 use std::mem::ManuallyDrop;
 use std::os::raw::c_char;
+use std::os::raw::c_void;
 use std::os::unix::ffi::OsStringExt as _;
+use std::path::Path;
 use std::path::PathBuf;
 use std::ptr;
 use std::slice;
+use std::sync::Mutex;
+use std::sync::OnceLock;
 
+use blazesym::helper::read_elf_build_id;
 use blazesym::normalize::Apk;
 use blazesym::normalize::Elf;
 use blazesym::normalize::NormalizeOpts;
@@ -20,6 +29,11 @@ use blazesym::normalize::Reason;
 use blazesym::normalize::Unknown;
 use blazesym::normalize::UserMeta;
 use blazesym::normalize::UserOutput;
+use blazesym::symbolize::Elf as SymbolizeElf;
+use blazesym::symbolize::Input;
+use blazesym::symbolize::Source;
+use blazesym::symbolize::Symbolized;
+use blazesym::symbolize::Symbolizer;
 use blazesym::Addr;
 
 use crate::blaze_err;
@@ -98,9 +112,28 @@ pub struct blaze_normalize_opts {
     /// using them for file look up) symbolic paths are probably the
     /// better choice.
     pub map_files: bool,
+    /// Whether to emit the ELF build ID directly on each
+    /// [`blaze_normalized_output`] (via its `build_id`/`build_id_len`
+    /// members) in addition to the `metas` array.
+    ///
+    /// Setting this flag makes every output a self-describing
+    /// `(file_offset, build_id)` tuple, which is convenient when each frame
+    /// is serialized independently and shipped to a remote symbolization host.
+    /// It only has an effect if build ID reading is enabled on the normalizer
+    /// in the first place.
+    pub emit_build_id: bool,
+    /// Whether to populate the `reason`/`has_reason` members on each
+    /// [`blaze_normalized_output`] for addresses that were mapped to an ELF or
+    /// APK object but still lack some detail (e.g., a build ID could not be
+    /// read, or an APK offset could not be resolved to an inner ELF).
+    ///
+    /// This is opt-in so that the common path does not pay for the extra
+    /// bookkeeping. The reason strings flow through
+    /// [`blaze_normalize_reason_str`], just like failure reasons do.
+    pub report_reasons: bool,
     /// Unused member available for future expansion. Must be initialized
     /// to zero.
-    pub reserved: [u8; 6],
+    pub reserved: [u8; 4],
 }
 
 impl Default for blaze_normalize_opts {
@@ -109,7 +142,9 @@ impl Default for blaze_normalize_opts {
             type_size: size_of::<Self>(),
             sorted_addrs: false,
             map_files: false,
-            reserved: [0; 6],
+            emit_build_id: false,
+            report_reasons: false,
+            reserved: [0; 4],
         }
     }
 }
@@ -120,6 +155,8 @@ impl From<blaze_normalize_opts> for NormalizeOpts {
             type_size: _,
             sorted_addrs,
             map_files,
+            emit_build_id: _,
+            report_reasons: _,
             reserved: _,
         } = opts;
         Self {
@@ -149,7 +186,11 @@ pub extern "C" fn blaze_normalizer_new() -> *mut blaze_normalizer {
     let normalizer = Normalizer::new();
     let normalizer_box = Box::new(normalizer);
     let () = set_last_err(blaze_err::BLAZE_ERR_OK);
-    Box::into_raw(normalizer_box)
+    let ptr = Box::into_raw(normalizer_box);
+    // The default configuration does not cache maps (see
+    // `blaze_normalizer_opts::default`).
+    let () = register_normalizer(ptr, false);
+    ptr
 }
 
 
@@ -190,7 +231,9 @@ pub unsafe extern "C" fn blaze_normalizer_new_opts(
         .build();
     let normalizer_box = Box::new(normalizer);
     let () = set_last_err(blaze_err::BLAZE_ERR_OK);
-    Box::into_raw(normalizer_box)
+    let ptr = Box::into_raw(normalizer_box);
+    let () = register_normalizer(ptr, cache_maps);
+    ptr
 }
 
 
@@ -205,6 +248,7 @@ pub unsafe extern "C" fn blaze_normalizer_new_opts(
 #[no_mangle]
 pub unsafe extern "C" fn blaze_normalizer_free(normalizer: *mut blaze_normalizer) {
     if !normalizer.is_null() {
+        let () = unregister_normalizer(normalizer);
         // SAFETY: The caller needs to ensure that `normalizer` is a
         //         valid pointer.
         drop(unsafe { Box::from_raw(normalizer) });
@@ -219,14 +263,51 @@ pub unsafe extern "C" fn blaze_normalizer_free(normalizer: *mut blaze_normalizer
 #[derive(Debug)]
 pub struct blaze_normalized_output {
     /// The file offset or non-normalized address.
+    ///
+    /// When the corresponding [`blaze_user_meta`] is an ELF object, this is an
+    /// ELF file offset and can be handed, together with the meta's path or
+    /// build ID, directly to [`blaze_symbolize_elf_file_offsets`] to
+    /// recover symbol information — closing the
+    /// normalize → transport → symbolize loop without re-deriving virtual
+    /// addresses.
     pub output: u64,
     /// The index into the associated [`blaze_user_meta`] array.
     pub meta_idx: usize,
+    /// The length of `build_id`, in bytes.
+    ///
+    /// This member is only populated when the
+    /// [`blaze_normalize_opts::emit_build_id`] flag was set; it is `0`
+    /// otherwise.
+    pub build_id_len: usize,
+    /// The ELF build ID of the object this output belongs to, if requested
+    /// and available.
+    ///
+    /// This member is only populated when the
+    /// [`blaze_normalize_opts::emit_build_id`] flag was set and the
+    /// corresponding meta is an ELF object with a known build ID; it is
+    /// `NULL` otherwise.
+    pub build_id: *mut u8,
+    /// Whether `reason` carries a meaningful value.
+    ///
+    /// This is only ever `true` when the
+    /// [`blaze_normalize_opts::report_reasons`] flag was set and the address
+    /// was mapped but with a caveat worth reporting.
+    pub has_reason: bool,
+    /// A hint at why a successfully mapped address still lacks some detail
+    /// (e.g., a build ID). Only meaningful when `has_reason` is `true`.
+    pub reason: blaze_normalize_reason,
 }
 
 impl From<(u64, usize)> for blaze_normalized_output {
     fn from((output, meta_idx): (u64, usize)) -> Self {
-        Self { output, meta_idx }
+        Self {
+            output,
+            meta_idx,
+            build_id_len: 0,
+            build_id: ptr::null_mut(),
+            has_reason: false,
+            reason: blaze_normalize_reason::BLAZE_NORMALIZE_REASON_UNMAPPED,
+        }
     }
 }
 
@@ -371,6 +452,10 @@ pub enum blaze_normalize_reason {
     BLAZE_NORMALIZE_REASON_MISSING_COMPONENT,
     /// The address belonged to an entity that is currently unsupported.
     BLAZE_NORMALIZE_REASON_UNSUPPORTED,
+    /// The object being denormalized is not currently mapped into the target
+    /// process. This reason is only ever reported by
+    /// [`blaze_denormalize_user_addrs`].
+    BLAZE_NORMALIZE_REASON_NOT_MAPPED,
 }
 
 impl From<Reason> for blaze_normalize_reason {
@@ -402,6 +487,9 @@ pub extern "C" fn blaze_normalize_reason_str(err: blaze_normalize_reason) -> *co
         e if e == BLAZE_NORMALIZE_REASON_UNSUPPORTED as i32 => {
             Reason::Unsupported.as_bytes().as_ptr().cast()
         }
+        e if e == BLAZE_NORMALIZE_REASON_NOT_MAPPED as i32 => {
+            b"object is not currently mapped\0".as_ptr().cast()
+        }
         _ => b"unknown reason\0".as_ptr().cast(),
     }
 }
@@ -532,13 +620,69 @@ pub struct blaze_normalized_user_output {
 }
 
 impl blaze_normalized_user_output {
-    fn from(other: UserOutput) -> ManuallyDrop<Self> {
+    fn from(other: UserOutput, emit_build_id: bool, report_reasons: bool) -> ManuallyDrop<Self> {
+        Self::from_parts(other.meta, other.outputs, emit_build_id, report_reasons)
+    }
+
+    /// Best-effort hint at why a mapped meta still lacks some detail, or `None`
+    /// if there is nothing worth reporting.
+    fn meta_caveat(meta: &UserMeta) -> Option<blaze_normalize_reason> {
+        match meta {
+            // We have an ELF meta but no build ID. That on its own is not a
+            // caveat: the caller may simply not have enabled build-ID reading,
+            // in which case the object may well carry one. Only flag the cases
+            // where a build ID is genuinely unavailable — the file cannot be
+            // read, or it has no `.note.gnu.build-id` (e.g. stripped). We
+            // probe the object directly to tell the two apart.
+            UserMeta::Elf(elf) if elf.build_id.is_none() => {
+                match read_elf_build_id(&elf.path) {
+                    // Build ID present; reading just was not requested.
+                    Ok(Some(_)) => None,
+                    // Unreadable or absent: a component really is missing.
+                    _ => Some(blaze_normalize_reason::BLAZE_NORMALIZE_REASON_MISSING_COMPONENT),
+                }
+            }
+            // An APK meta means the address was resolved to the archive but not
+            // to a specific inner ELF (otherwise an ELF meta would be reported).
+            UserMeta::Apk(_) => {
+                Some(blaze_normalize_reason::BLAZE_NORMALIZE_REASON_MISSING_COMPONENT)
+            }
+            _ => None,
+        }
+    }
+
+    /// Build a [`blaze_normalized_user_output`] from separately assembled
+    /// `metas` and `outputs`, used both by the single-PID conversion above and
+    /// by the multi-PID batch path which merges several process outputs.
+    fn from_parts(
+        metas: Vec<UserMeta>,
+        outputs: Vec<(u64, usize)>,
+        emit_build_id: bool,
+        report_reasons: bool,
+    ) -> ManuallyDrop<Self> {
+        // When requested, snapshot the per-meta build IDs up front so that we
+        // can attach them to the individual outputs below. We only pay for
+        // this when the flag is set, to avoid regressing the common path.
+        let build_ids = emit_build_id.then(|| {
+            metas
+                .iter()
+                .map(|meta| match meta {
+                    UserMeta::Elf(elf) => {
+                        elf.build_id.as_ref().map(|build_id| build_id.to_vec())
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        });
+        // Likewise snapshot per-meta caveats only when reasons were requested.
+        let reasons = report_reasons
+            .then(|| metas.iter().map(Self::meta_caveat).collect::<Vec<_>>());
+
         let slf = Self {
-            meta_cnt: other.meta.len(),
+            meta_cnt: metas.len(),
             metas: unsafe {
                 Box::into_raw(
-                    other
-                        .meta
+                    metas
                         .into_iter()
                         .map(blaze_user_meta::from)
                         .map(ManuallyDrop::into_inner)
@@ -549,13 +693,36 @@ impl blaze_normalized_user_output {
                 .unwrap()
                 .as_mut_ptr()
             },
-            output_cnt: other.outputs.len(),
+            output_cnt: outputs.len(),
             outputs: unsafe {
                 Box::into_raw(
-                    other
-                        .outputs
+                    outputs
                         .into_iter()
-                        .map(blaze_normalized_output::from)
+                        .map(|(output, meta_idx)| {
+                            let mut norm = blaze_normalized_output::from((output, meta_idx));
+                            if let Some(build_id) = build_ids
+                                .as_ref()
+                                .and_then(|build_ids| build_ids[meta_idx].as_ref())
+                            {
+                                norm.build_id_len = build_id.len();
+                                // SAFETY: We know the pointer is valid because
+                                //         it came from a `Box`.
+                                norm.build_id = unsafe {
+                                    Box::into_raw(build_id.clone().into_boxed_slice())
+                                        .as_mut()
+                                        .unwrap()
+                                        .as_mut_ptr()
+                                };
+                            }
+                            if let Some(reason) = reasons
+                                .as_ref()
+                                .and_then(|reasons| reasons[meta_idx])
+                            {
+                                norm.has_reason = true;
+                                norm.reason = reason;
+                            }
+                            norm
+                        })
                         .collect::<Vec<_>>()
                         .into_boxed_slice(),
                 )
@@ -570,12 +737,22 @@ impl blaze_normalized_user_output {
 }
 
 
+/// The source of `/proc/<pid>/maps` data to normalize against.
+enum MapsSource<'dat> {
+    /// Read the live `/proc/<pid>/maps` of the process with this PID.
+    Pid(u32),
+    /// Parse a caller-provided `/proc/<pid>/maps` formatted blob.
+    Data(&'dat [u8]),
+}
+
 unsafe fn blaze_normalize_user_addrs_impl(
     normalizer: *const blaze_normalizer,
-    pid: u32,
+    source: MapsSource<'_>,
     addrs: *const Addr,
     addr_cnt: usize,
     opts: &NormalizeOpts,
+    emit_build_id: bool,
+    report_reasons: bool,
 ) -> *mut blaze_normalized_user_output {
     // SAFETY: The caller needs to ensure that `normalizer` is a valid
     //         pointer.
@@ -583,20 +760,120 @@ unsafe fn blaze_normalize_user_addrs_impl(
     // SAFETY: The caller needs to ensure that `addrs` is a valid pointer and
     //         that it points to `addr_cnt` elements.
     let addrs = unsafe { slice_from_user_array(addrs, addr_cnt) };
-    let result = normalizer.normalize_user_addrs_opts(pid.into(), &addrs, opts);
-    match result {
-        Ok(addrs) => {
-            let output_box = Box::new(ManuallyDrop::into_inner(
-                blaze_normalized_user_output::from(addrs),
-            ));
-            let () = set_last_err(blaze_err::BLAZE_ERR_OK);
-            Box::into_raw(output_box)
+    let (metas, outputs) = match source {
+        MapsSource::Pid(pid) => match normalizer.normalize_user_addrs_opts(pid.into(), &addrs, opts)
+        {
+            Ok(output) => (output.meta, output.outputs),
+            Err(err) => {
+                let () = set_last_err(err.kind().into());
+                return ptr::null_mut()
+            }
+        },
+        MapsSource::Data(maps) => normalize_from_maps(maps, &addrs, opts.build_ids),
+    };
+
+    let output_box = Box::new(ManuallyDrop::into_inner(
+        blaze_normalized_user_output::from_parts(metas, outputs, emit_build_id, report_reasons),
+    ));
+    let () = set_last_err(blaze_err::BLAZE_ERR_OK);
+    Box::into_raw(output_box)
+}
+
+
+/// Normalize a batch of addresses against a parsed `/proc/<pid>/maps` blob,
+/// entirely offline.
+///
+/// Each address is looked up in `maps`; when it falls inside a file-backed
+/// mapping the corresponding ELF file offset and meta are produced, otherwise
+/// an [`Unknown`] meta records why. Metas are deduplicated by backing path so
+/// that multiple addresses into the same object share one entry, mirroring what
+/// the PID-based path reports.
+///
+/// `build_ids` mirrors [`NormalizeOpts::build_ids`]: build IDs are only read
+/// from the backing files when it is set, matching the PID-based path.
+fn normalize_from_maps(
+    maps: &[u8],
+    addrs: &[Addr],
+    build_ids: bool,
+) -> (Vec<UserMeta>, Vec<(u64, usize)>) {
+    /// Whether `path` names a real file-backed object rather than a kernel
+    /// pseudo-mapping (`[heap]`, `[stack]`, `[vdso]`, `[anon:...]`, ...). The
+    /// PID-based path only treats genuine files as ELF objects.
+    fn is_file_backed(path: &str) -> bool {
+        !path.is_empty() && !path.starts_with('[')
+    }
+
+    /// Intern a meta keyed by `key`, deduplicating identical objects.
+    fn intern_meta(
+        keys: &mut Vec<String>,
+        metas: &mut Vec<UserMeta>,
+        key: String,
+        make: impl FnOnce() -> UserMeta,
+    ) -> usize {
+        if let Some(pos) = keys.iter().position(|existing| *existing == key) {
+            pos
+        } else {
+            let () = keys.push(key);
+            let () = metas.push(make());
+            metas.len() - 1
         }
-        Err(err) => {
-            let () = set_last_err(err.kind().into());
-            ptr::null_mut()
+    }
+
+    let data = String::from_utf8_lossy(maps);
+    let entries = parse_proc_maps(&data);
+
+    let mut metas = Vec::new();
+    let mut keys = Vec::new();
+    let mut outputs = Vec::with_capacity(addrs.len());
+
+    for &addr in addrs {
+        match entries.iter().find(|entry| addr >= entry.start && addr < entry.end) {
+            // A file-backed mapping: report the ELF file offset.
+            Some(entry) if is_file_backed(&entry.path) => {
+                let file_offset = addr - entry.start + entry.offset;
+                let path = entry.path.clone();
+                let idx = intern_meta(&mut keys, &mut metas, format!("elf:{path}"), || {
+                    let build_id = build_ids
+                        .then(|| {
+                            read_elf_build_id(Path::new(&path))
+                                .ok()
+                                .flatten()
+                                .map(|build_id| Cow::Owned(build_id.as_ref().to_vec()))
+                        })
+                        .flatten();
+                    UserMeta::Elf(Elf {
+                        path: PathBuf::from(&path),
+                        build_id,
+                        _non_exhaustive: (),
+                    })
+                });
+                let () = outputs.push((file_offset, idx));
+            }
+            // A mapping without a backing file (anonymous, `[heap]`, `[vdso]`, ...).
+            Some(_) => {
+                let idx =
+                    intern_meta(&mut keys, &mut metas, String::from("missing-component"), || {
+                        UserMeta::Unknown(Unknown {
+                            reason: Reason::MissingComponent,
+                            _non_exhaustive: (),
+                        })
+                    });
+                let () = outputs.push((addr, idx));
+            }
+            // No mapping covers this address.
+            None => {
+                let idx = intern_meta(&mut keys, &mut metas, String::from("unmapped"), || {
+                    UserMeta::Unknown(Unknown {
+                        reason: Reason::Unmapped,
+                        _non_exhaustive: (),
+                    })
+                });
+                let () = outputs.push((addr, idx));
+            }
         }
     }
+
+    (metas, outputs)
 }
 
 
@@ -626,7 +903,17 @@ pub unsafe extern "C" fn blaze_normalize_user_addrs(
 ) -> *mut blaze_normalized_user_output {
     let opts = NormalizeOpts::default();
 
-    unsafe { blaze_normalize_user_addrs_impl(normalizer, pid, addrs, addr_cnt, &opts) }
+    unsafe {
+        blaze_normalize_user_addrs_impl(
+            normalizer,
+            MapsSource::Pid(pid),
+            addrs,
+            addr_cnt,
+            &opts,
+            false,
+            false,
+        )
+    }
 }
 
 
@@ -662,9 +949,367 @@ pub unsafe extern "C" fn blaze_normalize_user_addrs_opts(
         return ptr::null_mut()
     }
     let opts = input_sanitize!(opts, blaze_normalize_opts);
+    let emit_build_id = opts.emit_build_id;
+    let report_reasons = opts.report_reasons;
+    let opts = NormalizeOpts::from(opts);
+
+    unsafe {
+        blaze_normalize_user_addrs_impl(
+            normalizer,
+            MapsSource::Pid(pid),
+            addrs,
+            addr_cnt,
+            &opts,
+            emit_build_id,
+            report_reasons,
+        )
+    }
+}
+
+
+/// Normalize a list of user space addresses against a caller-provided
+/// `/proc/<pid>/maps` snapshot.
+///
+/// Unlike [`blaze_normalize_user_addrs_opts`], this function does not read the
+/// live `/proc/<pid>/maps` of a running process. Instead it parses `maps_data`,
+/// a blob in the exact textual format of `/proc/<pid>/maps` (one mapping per
+/// line: `start-end perms offset dev inode path`). This makes it suitable for
+/// the crash-time-capture / deferred-symbolication workflow, where a process
+/// dumps its maps when it faults and normalization happens later on a
+/// potentially different machine, long after the process is gone.
+///
+/// `opts` should point to a valid [`blaze_normalize_opts`] object.
+///
+/// On success, the function creates a new [`blaze_normalized_user_output`]
+/// object and returns it. The resulting object should be released using
+/// [`blaze_user_output_free`] once it is no longer needed.
+///
+/// On error, the function returns `NULL` and sets the thread's last error to
+/// indicate the problem encountered. Use [`blaze_err_last`] to retrieve this
+/// error.
+///
+/// # Safety
+/// - `maps_data` needs to be a valid pointer to `maps_len` bytes
+/// - `addrs` needs to be a valid pointer to `addr_cnt` addresses
+#[no_mangle]
+pub unsafe extern "C" fn blaze_normalize_user_addrs_from_maps(
+    normalizer: *const blaze_normalizer,
+    maps_data: *const u8,
+    maps_len: usize,
+    addrs: *const Addr,
+    addr_cnt: usize,
+    opts: *const blaze_normalize_opts,
+) -> *mut blaze_normalized_user_output {
+    if !input_zeroed!(opts, blaze_normalize_opts) {
+        let () = set_last_err(blaze_err::BLAZE_ERR_INVALID_INPUT);
+        return ptr::null_mut()
+    }
+    let opts = input_sanitize!(opts, blaze_normalize_opts);
+    let emit_build_id = opts.emit_build_id;
+    let report_reasons = opts.report_reasons;
+    let opts = NormalizeOpts::from(opts);
+
+    // SAFETY: The caller needs to ensure that `maps_data` is a valid pointer
+    //         and that it points to `maps_len` elements.
+    let maps = unsafe { slice_from_user_array(maps_data, maps_len) };
+    unsafe {
+        blaze_normalize_user_addrs_impl(
+            normalizer,
+            MapsSource::Data(&maps),
+            addrs,
+            addr_cnt,
+            &opts,
+            emit_build_id,
+            report_reasons,
+        )
+    }
+}
+
+
+/// A single input to [`blaze_normalize_user_addrs_multi`]: an address together
+/// with the index of the PID (into the parallel `pids` array) that it belongs
+/// to.
+#[repr(C)]
+#[derive(Debug)]
+pub struct blaze_normalize_addr {
+    /// The absolute user space address to normalize.
+    pub addr: Addr,
+    /// The index into the `pids` array identifying the process this address
+    /// belongs to.
+    pub pid_idx: usize,
+}
+
+
+/// Derive a stable key for deduplicating metas seen across processes.
+fn meta_dedup_key(meta: &UserMeta) -> String {
+    match meta {
+        UserMeta::Apk(apk) => format!("apk:{}", apk.path.display()),
+        UserMeta::Elf(elf) => format!("elf:{}:{:?}", elf.path.display(), elf.build_id),
+        UserMeta::Unknown(unknown) => format!("unknown:{:?}", unknown.reason),
+        _ => String::from("other"),
+    }
+}
+
+
+/// Normalize a batch of user space addresses spanning several processes.
+///
+/// C ABI compatible batch variant of [`blaze_normalize_user_addrs_opts`] for
+/// the common case where collected addresses (e.g., from a crash dump or a
+/// profiler) belong to more than one process. Each [`blaze_normalize_addr`]
+/// carries a `pid_idx` into the `pids` array, so a single call normalizes a
+/// heterogeneous batch while sharing the normalizer's maps and build-ID caches
+/// across PIDs.
+///
+/// The result is a single [`blaze_normalized_user_output`] whose `metas` array
+/// is unified and deduplicated across processes (identical ELF/APK mappings
+/// seen under different PIDs appear once), with `meta_idx` values pointing into
+/// it. Outputs are reported in the same order as the input `addrs`.
+///
+/// `opts` should point to a valid [`blaze_normalize_opts`] object.
+///
+/// On success, the function creates a new [`blaze_normalized_user_output`]
+/// object and returns it. The resulting object should be released using
+/// [`blaze_user_output_free`] once it is no longer needed.
+///
+/// On error, the function returns `NULL` and sets the thread's last error to
+/// indicate the problem encountered. Use [`blaze_err_last`] to retrieve this
+/// error.
+///
+/// # Safety
+/// - `pids` needs to be a valid pointer to `pid_cnt` PIDs
+/// - `addrs` needs to be a valid pointer to `addr_cnt` [`blaze_normalize_addr`]
+///   objects
+#[no_mangle]
+pub unsafe extern "C" fn blaze_normalize_user_addrs_multi(
+    normalizer: *const blaze_normalizer,
+    pids: *const u32,
+    pid_cnt: usize,
+    addrs: *const blaze_normalize_addr,
+    addr_cnt: usize,
+    opts: *const blaze_normalize_opts,
+) -> *mut blaze_normalized_user_output {
+    if !input_zeroed!(opts, blaze_normalize_opts) {
+        let () = set_last_err(blaze_err::BLAZE_ERR_INVALID_INPUT);
+        return ptr::null_mut()
+    }
+    let opts = input_sanitize!(opts, blaze_normalize_opts);
+    let emit_build_id = opts.emit_build_id;
+    let report_reasons = opts.report_reasons;
+    let opts = NormalizeOpts::from(opts);
+
+    // SAFETY: The caller needs to ensure that `normalizer` is a valid pointer.
+    let normalizer = unsafe { &*normalizer };
+    // SAFETY: The caller needs to ensure that `pids` points to `pid_cnt`
+    //         elements.
+    let pids = unsafe { slice_from_user_array(pids, pid_cnt) };
+    // SAFETY: The caller needs to ensure that `addrs` points to `addr_cnt`
+    //         elements.
+    let addrs = unsafe { slice_from_user_array(addrs, addr_cnt) };
+
+    // Group the original address indices by the PID they belong to so that we
+    // can normalize each process' addresses in one shot, reusing the shared
+    // caches.
+    let mut groups = vec![Vec::new(); pid_cnt];
+    for (idx, addr) in addrs.iter().enumerate() {
+        let Some(group) = groups.get_mut(addr.pid_idx) else {
+            let () = set_last_err(blaze_err::BLAZE_ERR_INVALID_INPUT);
+            return ptr::null_mut()
+        };
+        let () = group.push(idx);
+    }
+
+    let mut metas = Vec::new();
+    let mut keys = Vec::new();
+    let mut outputs = vec![(0u64, 0usize); addr_cnt];
+
+    for (pid_idx, indices) in groups.iter().enumerate() {
+        if indices.is_empty() {
+            continue
+        }
+        let group_addrs = indices
+            .iter()
+            .map(|&idx| addrs[idx].addr)
+            .collect::<Vec<_>>();
+        let result = normalizer.normalize_user_addrs_opts(pids[pid_idx].into(), &group_addrs, &opts);
+        let output = match result {
+            Ok(output) => output,
+            Err(err) => {
+                let () = set_last_err(err.kind().into());
+                return ptr::null_mut()
+            }
+        };
+
+        // Merge this process' metas into the unified, deduplicated array,
+        // remembering how local indices map onto combined ones.
+        let mut remap = Vec::with_capacity(output.meta.len());
+        for meta in output.meta.into_iter() {
+            let key = meta_dedup_key(&meta);
+            if let Some(pos) = keys.iter().position(|existing| *existing == key) {
+                let () = remap.push(pos);
+            } else {
+                let () = keys.push(key);
+                let () = remap.push(metas.len());
+                let () = metas.push(meta);
+            }
+        }
+
+        for (local, (offset, meta_idx)) in output.outputs.into_iter().enumerate() {
+            outputs[indices[local]] = (offset, remap[meta_idx]);
+        }
+    }
+
+    let output_box = Box::new(ManuallyDrop::into_inner(
+        blaze_normalized_user_output::from_parts(metas, outputs, emit_build_id, report_reasons),
+    ));
+    let () = set_last_err(blaze_err::BLAZE_ERR_OK);
+    Box::into_raw(output_box)
+}
+
+
+/// The type of the callback invoked by [`blaze_normalize_user_addrs_stream`]
+/// for each resolved address.
+///
+/// The callback receives the index of the address in the input slice, a
+/// pointer to the (stack-allocated) [`blaze_normalized_output`] describing it,
+/// a pointer to the [`blaze_user_meta`] it refers to, and the opaque
+/// `user_data` passed through unchanged. Both pointers are only valid for the
+/// duration of the call and must not be retained or freed by the callback.
+pub type blaze_normalize_stream_cb = Option<
+    unsafe extern "C" fn(usize, *const blaze_normalized_output, *const blaze_user_meta, *mut c_void),
+>;
+
+
+/// Normalize a list of user space addresses, streaming each result to a
+/// callback instead of collecting them all.
+///
+/// This behaves like [`blaze_normalize_user_addrs_opts`] but, rather than
+/// returning a [`blaze_normalized_user_output`] with `metas` and `outputs`
+/// arrays, it invokes `cb` once per address, in input order. Each C-level
+/// output is built just before its callback and released right after it, so
+/// consumers can accumulate into their own structures. Normalization of the
+/// whole input still completes before the first callback is invoked, and its
+/// intermediate result for every address is held in memory until the function
+/// returns, so memory use grows with the input size. The same metadata deduplication
+/// that drives `meta_cnt` still applies, so the meta handed to the callback may
+/// be shared across several addresses.
+///
+/// `pid` should describe the PID of the process to which the addresses belong.
+/// It may be `0` if they belong to the calling process.
+///
+/// `opts` should point to a valid [`blaze_normalize_opts`] object and `cb`
+/// should be a non-`NULL` callback.
+///
+/// On success, the function returns `true`. On error, it returns `false` and
+/// sets the thread's last error to indicate the problem encountered. Use
+/// [`blaze_err_last`] to retrieve this error.
+///
+/// # Safety
+/// - `addrs` needs to be a valid pointer to `addr_cnt` addresses
+#[no_mangle]
+pub unsafe extern "C" fn blaze_normalize_user_addrs_stream(
+    normalizer: *const blaze_normalizer,
+    pid: u32,
+    addrs: *const Addr,
+    addr_cnt: usize,
+    opts: *const blaze_normalize_opts,
+    cb: blaze_normalize_stream_cb,
+    user_data: *mut c_void,
+) -> bool {
+    if !input_zeroed!(opts, blaze_normalize_opts) {
+        let () = set_last_err(blaze_err::BLAZE_ERR_INVALID_INPUT);
+        return false
+    }
+    let opts = input_sanitize!(opts, blaze_normalize_opts);
+    let emit_build_id = opts.emit_build_id;
+    let report_reasons = opts.report_reasons;
     let opts = NormalizeOpts::from(opts);
 
-    unsafe { blaze_normalize_user_addrs_impl(normalizer, pid, addrs, addr_cnt, &opts) }
+    let Some(cb) = cb else {
+        let () = set_last_err(blaze_err::BLAZE_ERR_INVALID_INPUT);
+        return false
+    };
+
+    // SAFETY: The caller needs to ensure that `normalizer` is a valid pointer.
+    let normalizer = unsafe { &*normalizer };
+    // SAFETY: The caller needs to ensure that `addrs` is a valid pointer and
+    //         that it points to `addr_cnt` elements.
+    let addrs = unsafe { slice_from_user_array(addrs, addr_cnt) };
+    let output = match normalizer.normalize_user_addrs_opts(pid.into(), &addrs, &opts) {
+        Ok(output) => output,
+        Err(err) => {
+            let () = set_last_err(err.kind().into());
+            return false
+        }
+    };
+
+    // Snapshot the per-meta extras before converting the (comparatively small,
+    // deduplicated) meta set to its C representation. We intentionally keep the
+    // metas around but stream the potentially huge `outputs` array one entry at
+    // a time.
+    let build_ids = emit_build_id.then(|| {
+        output
+            .meta
+            .iter()
+            .map(|meta| match meta {
+                UserMeta::Elf(elf) => elf.build_id.as_ref().map(|build_id| build_id.to_vec()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    });
+    let reasons = report_reasons.then(|| {
+        output
+            .meta
+            .iter()
+            .map(blaze_normalized_user_output::meta_caveat)
+            .collect::<Vec<_>>()
+    });
+    let metas = output
+        .meta
+        .into_iter()
+        .map(blaze_user_meta::from)
+        .map(ManuallyDrop::into_inner)
+        .collect::<Vec<_>>();
+
+    for (idx, (offset, meta_idx)) in output.outputs.into_iter().enumerate() {
+        let mut norm = blaze_normalized_output::from((offset, meta_idx));
+        if let Some(build_id) = build_ids
+            .as_ref()
+            .and_then(|build_ids| build_ids[meta_idx].as_ref())
+        {
+            norm.build_id_len = build_id.len();
+            // SAFETY: We know the pointer is valid because it came from a `Box`.
+            norm.build_id = unsafe {
+                Box::into_raw(build_id.clone().into_boxed_slice())
+                    .as_mut()
+                    .unwrap()
+                    .as_mut_ptr()
+            };
+        }
+        if let Some(reason) = reasons.as_ref().and_then(|reasons| reasons[meta_idx]) {
+            norm.has_reason = true;
+            norm.reason = reason;
+        }
+
+        // SAFETY: `cb` is a valid callback and the pointers are valid for the
+        //         duration of the call.
+        let () = unsafe { cb(idx, &norm, &metas[meta_idx], user_data) };
+
+        if !norm.build_id.is_null() {
+            let _build_id = unsafe {
+                Box::<[u8]>::from_raw(slice::from_raw_parts_mut(
+                    norm.build_id,
+                    norm.build_id_len,
+                ))
+            };
+        }
+    }
+
+    for meta in metas {
+        let () = unsafe { meta.free() };
+    }
+
+    let () = set_last_err(blaze_err::BLAZE_ERR_OK);
+    true
 }
 
 
@@ -691,7 +1336,7 @@ pub unsafe extern "C" fn blaze_user_output_free(output: *mut blaze_normalized_us
         ))
     }
     .into_vec();
-    let _norm_addrs = unsafe {
+    let norm_addrs = unsafe {
         Box::<[blaze_normalized_output]>::from_raw(slice::from_raw_parts_mut(
             user_output.outputs,
             user_output.output_cnt,
@@ -699,67 +1344,957 @@ pub unsafe extern "C" fn blaze_user_output_free(output: *mut blaze_normalized_us
     }
     .into_vec();
 
+    for norm_addr in norm_addrs {
+        if !norm_addr.build_id.is_null() {
+            // SAFETY: A non-NULL `build_id` was allocated as a boxed slice of
+            //         `build_id_len` bytes in `blaze_normalized_user_output::from_parts`
+            //         or while deserializing.
+            let _build_id = unsafe {
+                Box::<[u8]>::from_raw(slice::from_raw_parts_mut(
+                    norm_addr.build_id,
+                    norm_addr.build_id_len,
+                ))
+            };
+        }
+    }
+
     for addr_meta in addr_metas {
         let () = unsafe { addr_meta.free() };
     }
 }
 
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// A single input to [`blaze_denormalize_user_addrs`]: a normalized
+/// `(object, file offset)` pair to re-attach to a running process.
+///
+/// The object is identified either by `path` or by `build_id`; exactly one of
+/// the two should be provided.
+#[repr(C)]
+#[derive(Debug)]
+pub struct blaze_denormalize_addr {
+    /// The path to the ELF object, or `NULL` to identify it by `build_id`.
+    pub path: *const c_char,
+    /// The file offset within the object to denormalize.
+    pub file_offset: u64,
+    /// The length of `build_id`, in bytes.
+    pub build_id_len: usize,
+    /// The build ID identifying the object, or `NULL` to identify it by
+    /// `path`.
+    pub build_id: *const u8,
+    /// Unused member available for future expansion.
+    pub reserved: [u8; 8],
+}
 
-    use std::ffi::CStr;
-    use std::io;
-    use std::path::Path;
 
-    use blazesym::helper::read_elf_build_id;
+/// The result of denormalizing a single [`blaze_denormalize_addr`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct blaze_denormalized_output {
+    /// The live virtual address in the target process, or `0` if the object
+    /// could not be denormalized (in which case `reason` indicates why).
+    pub addr: Addr,
+    /// The reason denormalization failed. Only meaningful when `addr` is `0`.
+    pub reason: blaze_normalize_reason,
+    /// Unused member available for future expansion.
+    pub reserved: [u8; 7],
+}
 
-    use test_tag::tag;
 
-    use crate::blaze_err_last;
+/// An object representing denormalized (reverse-mapped) addresses.
+///
+/// This is the inverse of [`blaze_normalized_user_output`]: it turns recorded
+/// `(object, file offset)` pairs back into live virtual addresses for a running
+/// process.
+#[repr(C)]
+#[derive(Debug)]
+pub struct blaze_denormalized_user_output {
+    /// The number of [`blaze_denormalized_output`] objects present in
+    /// `outputs`.
+    pub output_cnt: usize,
+    /// An array of `output_cnt` objects, one per input address and in the same
+    /// order.
+    pub outputs: *mut blaze_denormalized_output,
+    /// Unused member available for future expansion.
+    pub reserved: [u8; 8],
+}
 
 
-    /// Check that various types have expected sizes.
-    #[test]
-    #[cfg(target_pointer_width = "64")]
-    fn type_sizes() {
-        assert_eq!(size_of::<blaze_normalizer_opts>(), 16);
-        assert_eq!(size_of::<blaze_normalize_opts>(), 16);
-        assert_eq!(size_of::<blaze_user_meta_apk>(), 16);
-        assert_eq!(size_of::<blaze_user_meta_elf>(), 32);
-        assert_eq!(size_of::<blaze_user_meta_unknown>(), 8);
-    }
+/// A single parsed `/proc/<pid>/maps` entry.
+struct MapsEntry {
+    start: u64,
+    end: u64,
+    offset: u64,
+    path: String,
+}
 
-    /// Exercise the `Debug` representation of various types.
-    #[tag(miri)]
-    #[test]
-    fn debug_repr() {
-        let output = blaze_normalized_output {
-            output: 0x1337,
-            meta_idx: 1,
+/// Parse the textual `/proc/<pid>/maps` format into its entries, including
+/// those without a backing path.
+fn parse_proc_maps(data: &str) -> Vec<MapsEntry> {
+    let mut entries = Vec::new();
+    for line in data.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(range) = fields.next() else { continue };
+        let Some((start, end)) = range.split_once('-') else {
+            continue
         };
-        assert_eq!(
-            format!("{output:?}"),
-            "blaze_normalized_output { output: 4919, meta_idx: 1 }"
-        );
+        // perms
+        let _ = fields.next();
+        let Some(offset) = fields.next() else { continue };
+        // dev, inode
+        let _ = fields.next();
+        let _ = fields.next();
+        // The remainder of the line is the (possibly empty) path. Entries
+        // without a backing path are kept so that callers can tell an
+        // anonymous mapping apart from an address that is not mapped at all.
+        let path = fields.collect::<Vec<_>>().join(" ");
+        let (Ok(start), Ok(end), Ok(offset)) = (
+            u64::from_str_radix(start, 16),
+            u64::from_str_radix(end, 16),
+            u64::from_str_radix(offset, 16),
+        ) else {
+            continue
+        };
+        let () = entries.push(MapsEntry {
+            start,
+            end,
+            offset,
+            path,
+        });
+    }
+    entries
+}
 
-        let meta_kind = blaze_user_meta_kind::BLAZE_USER_META_APK;
-        assert_eq!(format!("{meta_kind:?}"), "BLAZE_USER_META_APK");
+/// Per-normalizer state mirroring the `cache_maps` configuration so that the
+/// denormalization path honors the same option the normalization path does.
+struct NormalizerState {
+    /// Whether `/proc/<pid>/maps` contents may be cached and reused.
+    cache_maps: bool,
+    /// Parsed maps keyed by PID, populated only when `cache_maps` is set.
+    maps: HashMap<u32, Vec<MapsEntry>>,
+}
 
-        let apk = blaze_user_meta_apk {
-            path: ptr::null_mut(),
-            reserved: [0u8; 8],
-        };
-        assert_eq!(
-            format!("{apk:?}"),
-            "blaze_user_meta_apk { path: 0x0, reserved: [0, 0, 0, 0, 0, 0, 0, 0] }",
-        );
+/// Side table mapping a [`blaze_normalizer`] pointer onto its capi-level
+/// configuration. blazesym's [`Normalizer`] is opaque and does not expose its
+/// options, so we record them here when the normalizer is created.
+static NORMALIZER_STATE: OnceLock<Mutex<HashMap<usize, NormalizerState>>> = OnceLock::new();
+
+/// Register a freshly created normalizer's `cache_maps` setting.
+fn register_normalizer(normalizer: *const blaze_normalizer, cache_maps: bool) {
+    let mut states = NORMALIZER_STATE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap();
+    let _ = states.insert(
+        normalizer as usize,
+        NormalizerState {
+            cache_maps,
+            maps: HashMap::new(),
+        },
+    );
+}
 
-        let elf = blaze_user_meta_elf {
-            path: ptr::null_mut(),
-            build_id_len: 0,
-            build_id: ptr::null_mut(),
+/// Forget a normalizer's recorded state when it is freed.
+fn unregister_normalizer(normalizer: *const blaze_normalizer) {
+    if let Some(states) = NORMALIZER_STATE.get() {
+        let _ = states.lock().unwrap().remove(&(normalizer as usize));
+    }
+}
+
+/// Invoke `f` with the parsed `/proc/<pid>/maps` of `pid`, reusing a cached copy
+/// when the normalizer was configured with `cache_maps` and reading fresh
+/// contents otherwise. This mirrors how the normalization path consults the
+/// normalizer's maps cache.
+fn with_proc_maps<R>(
+    normalizer: *const blaze_normalizer,
+    pid: u32,
+    f: impl FnOnce(&[MapsEntry]) -> R,
+) -> io::Result<R> {
+    let cache_maps = NORMALIZER_STATE
+        .get()
+        .and_then(|states| {
+            states
+                .lock()
+                .unwrap()
+                .get(&(normalizer as usize))
+                .map(|state| state.cache_maps)
+        })
+        .unwrap_or(false);
+
+    if cache_maps {
+        let states = NORMALIZER_STATE.get_or_init(|| Mutex::new(HashMap::new()));
+        let mut states = states.lock().unwrap();
+        let state = states
+            .entry(normalizer as usize)
+            .or_insert_with(|| NormalizerState {
+                cache_maps,
+                maps: HashMap::new(),
+            });
+        if !state.maps.contains_key(&pid) {
+            let data = fs::read_to_string(proc_maps_path(pid))?;
+            let _ = state.maps.insert(pid, parse_proc_maps(&data));
+        }
+        Ok(f(&state.maps[&pid]))
+    } else {
+        let data = fs::read_to_string(proc_maps_path(pid))?;
+        let entries = parse_proc_maps(&data);
+        Ok(f(&entries))
+    }
+}
+
+/// The `/proc/<pid>/maps` path for `pid`, or the calling process when `pid` is
+/// `0`.
+fn proc_maps_path(pid: u32) -> PathBuf {
+    if pid == 0 {
+        PathBuf::from("/proc/self/maps")
+    } else {
+        PathBuf::from(format!("/proc/{pid}/maps"))
+    }
+}
+
+/// Compute the live virtual address for `file_offset` within the object
+/// matching `input`, or `None` if the object is not currently mapped such that
+/// the offset falls inside a backing segment.
+fn denormalize_one(entries: &[MapsEntry], input: &blaze_denormalize_addr) -> Option<Addr> {
+    // SAFETY: The caller guarantees a valid (optional) NUL terminated `path`.
+    let path = (!input.path.is_null()).then(|| unsafe { CStr::from_ptr(input.path) });
+    let build_id = (!input.build_id.is_null()).then(|| {
+        // SAFETY: A non-NULL `build_id` points to `build_id_len` valid bytes.
+        unsafe { slice::from_raw_parts(input.build_id, input.build_id_len) }
+    });
+
+    for entry in entries {
+        let matches = if let Some(path) = path {
+            path.to_bytes() == entry.path.as_bytes()
+        } else if let Some(build_id) = build_id {
+            read_elf_build_id(Path::new(&entry.path))
+                .ok()
+                .flatten()
+                .map(|id| id.as_ref() == build_id)
+                .unwrap_or(false)
+        } else {
+            false
+        };
+
+        if !matches {
+            continue
+        }
+
+        let size = entry.end - entry.start;
+        if input.file_offset >= entry.offset && input.file_offset < entry.offset + size {
+            return Some(entry.start + (input.file_offset - entry.offset))
+        }
+    }
+    None
+}
+
+
+/// Denormalize a list of normalized `(object, file offset)` pairs back to live
+/// virtual addresses in a running process.
+///
+/// This is the inverse of [`blaze_normalize_user_addrs`]: offline tooling that
+/// recorded normalized `(build-id or path, file offset)` pairs can re-attach
+/// them to a currently running process. Each input is looked up in the target
+/// process' `/proc/<pid>/maps` and, if the object is mapped such that the file
+/// offset falls inside a backing segment, the corresponding virtual address is
+/// reported. Otherwise the output's `addr` is `0` and its `reason` is
+/// [`blaze_normalize_reason::BLAZE_NORMALIZE_REASON_NOT_MAPPED`].
+///
+/// `pid` should describe the PID of the target process. It may be `0` if the
+/// addresses belong to the calling process.
+///
+/// On success, the function creates a new [`blaze_denormalized_user_output`]
+/// object and returns it. The resulting object should be released using
+/// [`blaze_denormalize_user_output_free`] once it is no longer needed.
+///
+/// On error, the function returns `NULL` and sets the thread's last error to
+/// indicate the problem encountered. Use [`blaze_err_last`] to retrieve this
+/// error.
+///
+/// # Safety
+/// - `addrs` needs to be a valid pointer to `addr_cnt`
+///   [`blaze_denormalize_addr`] objects
+#[no_mangle]
+pub unsafe extern "C" fn blaze_denormalize_user_addrs(
+    normalizer: *const blaze_normalizer,
+    pid: u32,
+    addrs: *const blaze_denormalize_addr,
+    addr_cnt: usize,
+) -> *mut blaze_denormalized_user_output {
+    if normalizer.is_null() {
+        let () = set_last_err(blaze_err::BLAZE_ERR_INVALID_INPUT);
+        return ptr::null_mut()
+    }
+    // SAFETY: The caller needs to ensure that `addrs` is a valid pointer and
+    //         that it points to `addr_cnt` elements.
+    let addrs = unsafe { slice_from_user_array(addrs, addr_cnt) };
+
+    // Read the target's maps through the normalizer's caching machinery so that
+    // a `cache_maps`-configured normalizer reuses them across calls.
+    let result = with_proc_maps(normalizer, pid, |entries| {
+        addrs
+            .iter()
+            .map(|input| match denormalize_one(entries, input) {
+                Some(addr) => blaze_denormalized_output {
+                    addr,
+                    // On success `reason` is not meaningful; leave it at the
+                    // zero discriminant rather than reporting a spurious
+                    // failure reason.
+                    reason: blaze_normalize_reason::BLAZE_NORMALIZE_REASON_UNMAPPED,
+                    reserved: [0u8; 7],
+                },
+                None => blaze_denormalized_output {
+                    addr: 0,
+                    reason: blaze_normalize_reason::BLAZE_NORMALIZE_REASON_NOT_MAPPED,
+                    reserved: [0u8; 7],
+                },
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice()
+    });
+    let outputs = match result {
+        Ok(outputs) => outputs,
+        Err(err) => {
+            let err = blazesym::Error::from(err);
+            let () = set_last_err(err.kind().into());
+            return ptr::null_mut()
+        }
+    };
+
+    let output = blaze_denormalized_user_output {
+        output_cnt: outputs.len(),
+        outputs: unsafe { Box::into_raw(outputs).as_mut().unwrap().as_mut_ptr() },
+        reserved: [0u8; 8],
+    };
+    let () = set_last_err(blaze_err::BLAZE_ERR_OK);
+    Box::into_raw(Box::new(output))
+}
+
+
+/// Free an object as returned by [`blaze_denormalize_user_addrs`].
+///
+/// # Safety
+/// The provided object should have been created by
+/// [`blaze_denormalize_user_addrs`].
+#[no_mangle]
+pub unsafe extern "C" fn blaze_denormalize_user_output_free(
+    output: *mut blaze_denormalized_user_output,
+) {
+    if output.is_null() {
+        return
+    }
+
+    // SAFETY: The caller should make sure that `output` was created by
+    //         `blaze_denormalize_user_addrs`.
+    let output = unsafe { Box::from_raw(output) };
+    let _outputs = unsafe {
+        Box::<[blaze_denormalized_output]>::from_raw(slice::from_raw_parts_mut(
+            output.outputs,
+            output.output_cnt,
+        ))
+    };
+}
+
+
+/// Symbol information resolved from an ELF file offset.
+///
+/// This is the companion to [`blaze_normalized_output`]: the file offsets it
+/// produces can be handed straight back here to recover symbol names.
+#[repr(C)]
+#[derive(Debug)]
+pub struct blaze_sym_file_offset {
+    /// The symbol name, or `NULL` if the offset could not be resolved.
+    pub name: *mut c_char,
+    /// The start address (in the ELF file's address space) of the symbol.
+    pub addr: Addr,
+    /// The offset of the queried file offset relative to the symbol's start.
+    pub offset: u64,
+    /// Unused member available for future expansion.
+    pub reserved: [u8; 8],
+}
+
+
+/// The result of [`blaze_symbolize_elf_file_offsets`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct blaze_syms_file_offsets {
+    /// The number of [`blaze_sym_file_offset`] objects present in `syms`.
+    pub cnt: usize,
+    /// An array of `cnt` objects, one per queried file offset and in the same
+    /// order.
+    pub syms: *mut blaze_sym_file_offset,
+    /// Unused member available for future expansion.
+    pub reserved: [u8; 8],
+}
+
+
+/// Symbolize a list of ELF file offsets against an ELF object.
+///
+/// This closes the normalize → transport → symbolize loop: feed the `output`
+/// values from [`blaze_normalized_output`], together with the backing ELF
+/// path, straight into this function to recover symbol names without
+/// re-deriving virtual addresses. Offsets that cannot be resolved produce an
+/// entry whose `name` is `NULL`.
+///
+/// On success, the function creates a new [`blaze_syms_file_offsets`] object
+/// and returns it. The resulting object should be released using
+/// [`blaze_syms_file_offsets_free`] once it is no longer needed.
+///
+/// On error, the function returns `NULL` and sets the thread's last error to
+/// indicate the problem encountered. Use [`blaze_err_last`] to retrieve this
+/// error.
+///
+/// # Safety
+/// - `path` needs to be a valid pointer to a NUL terminated string
+/// - `file_offsets` needs to be a valid pointer to `cnt` offsets
+#[no_mangle]
+pub unsafe extern "C" fn blaze_symbolize_elf_file_offsets(
+    path: *const c_char,
+    file_offsets: *const u64,
+    cnt: usize,
+) -> *mut blaze_syms_file_offsets {
+    if path.is_null() {
+        let () = set_last_err(blaze_err::BLAZE_ERR_INVALID_INPUT);
+        return ptr::null_mut()
+    }
+
+    // SAFETY: The caller guarantees `path` is a valid NUL terminated string.
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = PathBuf::from(OsString::from_vec(path.to_bytes().to_vec()));
+    // SAFETY: The caller guarantees `file_offsets` points to `cnt` elements.
+    let offsets = unsafe { slice_from_user_array(file_offsets, cnt) };
+
+    let symbolizer = Symbolizer::new();
+    let src = Source::Elf(SymbolizeElf::new(path));
+    let result = symbolizer.symbolize(&src, Input::FileOffset(&offsets));
+    let symbolized = match result {
+        Ok(symbolized) => symbolized,
+        Err(err) => {
+            let () = set_last_err(err.kind().into());
+            return ptr::null_mut()
+        }
+    };
+
+    let syms = symbolized
+        .into_iter()
+        .map(|sym| match sym {
+            Symbolized::Sym(sym) => blaze_sym_file_offset {
+                name: CString::new(sym.name.into_owned())
+                    .expect("encountered symbol name with NUL bytes")
+                    .into_raw(),
+                addr: sym.addr,
+                offset: sym.offset as u64,
+                reserved: [0u8; 8],
+            },
+            _ => blaze_sym_file_offset {
+                name: ptr::null_mut(),
+                addr: 0,
+                offset: 0,
+                reserved: [0u8; 8],
+            },
+        })
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    let output = blaze_syms_file_offsets {
+        cnt: syms.len(),
+        syms: unsafe { Box::into_raw(syms).as_mut().unwrap().as_mut_ptr() },
+        reserved: [0u8; 8],
+    };
+    let () = set_last_err(blaze_err::BLAZE_ERR_OK);
+    Box::into_raw(Box::new(output))
+}
+
+
+/// Free an object as returned by [`blaze_symbolize_elf_file_offsets`].
+///
+/// # Safety
+/// The provided object should have been created by
+/// [`blaze_symbolize_elf_file_offsets`].
+#[no_mangle]
+pub unsafe extern "C" fn blaze_syms_file_offsets_free(syms: *mut blaze_syms_file_offsets) {
+    if syms.is_null() {
+        return
+    }
+
+    // SAFETY: The caller should make sure that `syms` was created by
+    //         `blaze_symbolize_elf_file_offsets`.
+    let syms = unsafe { Box::from_raw(syms) };
+    let entries = unsafe {
+        Box::<[blaze_sym_file_offset]>::from_raw(slice::from_raw_parts_mut(syms.syms, syms.cnt))
+    }
+    .into_vec();
+
+    for entry in entries {
+        if !entry.name.is_null() {
+            // SAFETY: A non-NULL `name` came from `CString::into_raw`.
+            drop(unsafe { CString::from_raw(entry.name) });
+        }
+    }
+}
+
+
+/// The magic prefix identifying a serialized [`blaze_normalized_user_output`].
+const BLAZE_NORM_SERIALIZE_MAGIC: [u8; 8] = *b"BLZNORM\x00";
+/// The current on-wire format version. Bump this whenever the layout changes
+/// so that older readers can reject newer blobs they do not understand.
+const BLAZE_NORM_SERIALIZE_VERSION: u32 = 2;
+
+
+/// A byte buffer as produced by [`blaze_normalized_user_output_serialize`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct blaze_normalized_output_buffer {
+    /// The serialized data. This member is never `NULL`.
+    pub data: *mut u8,
+    /// The number of bytes present in `data`.
+    pub len: usize,
+    /// Unused member available for future expansion.
+    pub reserved: [u8; 8],
+}
+
+
+fn put_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u64(buf: &mut Vec<u8>, value: u64) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    put_u64(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn put_opt_bytes(buf: &mut Vec<u8>, bytes: Option<&[u8]>) {
+    match bytes {
+        Some(bytes) => {
+            buf.push(1);
+            put_bytes(buf, bytes);
+        }
+        None => buf.push(0),
+    }
+}
+
+
+/// A minimal little-endian cursor used when deserializing.
+struct Cursor<'dat> {
+    data: &'dat [u8],
+    pos: usize,
+}
+
+impl<'dat> Cursor<'dat> {
+    fn new(data: &'dat [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'dat [u8]> {
+        let end = self.pos.checked_add(len)?;
+        let slice = self.data.get(self.pos..end)?;
+        self.pos = end;
+        Some(slice)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|slice| slice[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.take(size_of::<u32>())?;
+        Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        let bytes = self.take(size_of::<u64>())?;
+        Some(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn bytes(&mut self) -> Option<&'dat [u8]> {
+        let len = self.u64()? as usize;
+        self.take(len)
+    }
+
+    fn opt_bytes(&mut self) -> Option<Option<&'dat [u8]>> {
+        match self.u8()? {
+            0 => Some(None),
+            1 => Some(Some(self.bytes()?)),
+            _ => None,
+        }
+    }
+}
+
+
+/// Serialize a [`blaze_normalized_user_output`] into a portable, versioned byte
+/// buffer.
+///
+/// The produced blob is self-describing (leading magic and format version),
+/// little-endian, and independent of the host's pointer width, so a normalized
+/// result can be captured on one machine and symbolized on another. Feed the
+/// buffer back into [`blaze_normalized_user_output_deserialize`] to recover an
+/// equivalent object.
+///
+/// On success, the function returns a new [`blaze_normalized_output_buffer`]
+/// object. It should be released using
+/// [`blaze_normalized_output_buffer_free`] once it is no longer needed.
+///
+/// On error, the function returns `NULL` and sets the thread's last error to
+/// indicate the problem encountered. Use [`blaze_err_last`] to retrieve this
+/// error.
+///
+/// # Safety
+/// - `output` needs to be a valid pointer to a [`blaze_normalized_user_output`]
+///   object as created by one of the `blaze_normalize_user_addrs*` functions
+#[no_mangle]
+pub unsafe extern "C" fn blaze_normalized_user_output_serialize(
+    output: *const blaze_normalized_user_output,
+) -> *mut blaze_normalized_output_buffer {
+    if output.is_null() {
+        let () = set_last_err(blaze_err::BLAZE_ERR_INVALID_INPUT);
+        return ptr::null_mut()
+    }
+
+    // SAFETY: The caller guarantees that `output` is valid.
+    let output = unsafe { &*output };
+
+    let mut buf = Vec::new();
+    let () = buf.extend_from_slice(&BLAZE_NORM_SERIALIZE_MAGIC);
+    let () = put_u32(&mut buf, BLAZE_NORM_SERIALIZE_VERSION);
+
+    let () = put_u64(&mut buf, output.meta_cnt as u64);
+    // SAFETY: `metas` points to `meta_cnt` valid `blaze_user_meta` objects.
+    let metas = unsafe { slice::from_raw_parts(output.metas, output.meta_cnt) };
+    for meta in metas {
+        match meta.kind {
+            blaze_user_meta_kind::BLAZE_USER_META_UNKNOWN => {
+                let () = buf.push(0);
+                // SAFETY: The kind tells us the `unknown` variant is valid.
+                let unknown = unsafe { &meta.variant.unknown };
+                let () = buf.push(unknown.reason as u8);
+            }
+            blaze_user_meta_kind::BLAZE_USER_META_APK => {
+                let () = buf.push(1);
+                // SAFETY: The kind tells us the `apk` variant is valid.
+                let apk = unsafe { &meta.variant.apk };
+                // SAFETY: `path` is a valid NUL terminated string.
+                let path = unsafe { CStr::from_ptr(apk.path) };
+                let () = put_bytes(&mut buf, path.to_bytes());
+            }
+            blaze_user_meta_kind::BLAZE_USER_META_ELF => {
+                let () = buf.push(2);
+                // SAFETY: The kind tells us the `elf` variant is valid.
+                let elf = unsafe { &meta.variant.elf };
+                // SAFETY: `path` is a valid NUL terminated string.
+                let path = unsafe { CStr::from_ptr(elf.path) };
+                let () = put_bytes(&mut buf, path.to_bytes());
+                let build_id = (!elf.build_id.is_null()).then(|| {
+                    // SAFETY: A non-NULL `build_id` points to `build_id_len`
+                    //         valid bytes.
+                    unsafe { slice::from_raw_parts(elf.build_id, elf.build_id_len) }
+                });
+                let () = put_opt_bytes(&mut buf, build_id);
+            }
+        }
+    }
+
+    let () = put_u64(&mut buf, output.output_cnt as u64);
+    // SAFETY: `outputs` points to `output_cnt` valid objects.
+    let outputs = unsafe { slice::from_raw_parts(output.outputs, output.output_cnt) };
+    for out in outputs {
+        let () = put_u64(&mut buf, out.output);
+        let () = put_u64(&mut buf, out.meta_idx as u64);
+        let build_id = (!out.build_id.is_null()).then(|| {
+            // SAFETY: A non-NULL `build_id` points to `build_id_len` bytes.
+            unsafe { slice::from_raw_parts(out.build_id, out.build_id_len) }
+        });
+        let () = put_opt_bytes(&mut buf, build_id);
+        let () = buf.push(out.has_reason as u8);
+        let () = buf.push(out.reason as u8);
+    }
+
+    let mut boxed = buf.into_boxed_slice();
+    let buffer = blaze_normalized_output_buffer {
+        data: boxed.as_mut_ptr(),
+        len: boxed.len(),
+        reserved: [0u8; 8],
+    };
+    let () = std::mem::forget(boxed);
+    let () = set_last_err(blaze_err::BLAZE_ERR_OK);
+    Box::into_raw(Box::new(buffer))
+}
+
+
+/// Free a buffer as returned by [`blaze_normalized_user_output_serialize`].
+///
+/// # Safety
+/// The provided buffer should have been created by
+/// [`blaze_normalized_user_output_serialize`].
+#[no_mangle]
+pub unsafe extern "C" fn blaze_normalized_output_buffer_free(
+    buffer: *mut blaze_normalized_output_buffer,
+) {
+    if buffer.is_null() {
+        return
+    }
+
+    // SAFETY: The caller guarantees that `buffer` was created by us.
+    let buffer = unsafe { Box::from_raw(buffer) };
+    let _data = unsafe {
+        Box::<[u8]>::from_raw(slice::from_raw_parts_mut(buffer.data, buffer.len))
+    };
+}
+
+
+/// Deserialize a [`blaze_normalized_user_output`] from a buffer produced by
+/// [`blaze_normalized_user_output_serialize`].
+///
+/// On success, the function creates a new [`blaze_normalized_user_output`]
+/// object and returns it. The resulting object is indistinguishable from one
+/// produced by the `blaze_normalize_user_addrs*` functions and should be
+/// released using [`blaze_user_output_free`] once it is no longer needed.
+///
+/// On error (including a missing magic or an unsupported format version), the
+/// function returns `NULL` and sets the thread's last error to indicate the
+/// problem encountered. Use [`blaze_err_last`] to retrieve this error.
+///
+/// # Safety
+/// - `data` needs to be a valid pointer to `len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn blaze_normalized_user_output_deserialize(
+    data: *const u8,
+    len: usize,
+) -> *mut blaze_normalized_user_output {
+    // SAFETY: The caller guarantees that `data` points to `len` valid bytes.
+    let data = unsafe { slice_from_user_array(data, len) };
+    match deserialize_user_output(&data) {
+        Some(output) => {
+            let () = set_last_err(blaze_err::BLAZE_ERR_OK);
+            Box::into_raw(Box::new(ManuallyDrop::into_inner(output)))
+        }
+        None => {
+            let () = set_last_err(blaze_err::BLAZE_ERR_INVALID_INPUT);
+            ptr::null_mut()
+        }
+    }
+}
+
+
+/// Map a serialized discriminant back onto a [`blaze_normalize_reason`].
+fn reason_from_u8(value: u8) -> Option<blaze_normalize_reason> {
+    use blaze_normalize_reason::*;
+
+    match value {
+        v if v == BLAZE_NORMALIZE_REASON_UNMAPPED as u8 => Some(BLAZE_NORMALIZE_REASON_UNMAPPED),
+        v if v == BLAZE_NORMALIZE_REASON_MISSING_COMPONENT as u8 => {
+            Some(BLAZE_NORMALIZE_REASON_MISSING_COMPONENT)
+        }
+        v if v == BLAZE_NORMALIZE_REASON_UNSUPPORTED as u8 => {
+            Some(BLAZE_NORMALIZE_REASON_UNSUPPORTED)
+        }
+        v if v == BLAZE_NORMALIZE_REASON_NOT_MAPPED as u8 => {
+            Some(BLAZE_NORMALIZE_REASON_NOT_MAPPED)
+        }
+        _ => None,
+    }
+}
+
+
+fn deserialize_user_output(data: &[u8]) -> Option<ManuallyDrop<blaze_normalized_user_output>> {
+    /// Release C structures built from a rejected blob. Mirrors the freeing
+    /// logic in [`blaze_user_output_free`] so the `CString` paths and build-ID
+    /// buffers produced while parsing do not leak on an error path.
+    fn free_partial(metas: Vec<blaze_user_meta>, outputs: Vec<blaze_normalized_output>) {
+        for output in outputs {
+            if !output.build_id.is_null() {
+                // SAFETY: A non-NULL `build_id` was allocated below as a boxed
+                //         slice of `build_id_len` bytes.
+                let _build_id = unsafe {
+                    Box::<[u8]>::from_raw(slice::from_raw_parts_mut(
+                        output.build_id,
+                        output.build_id_len,
+                    ))
+                };
+            }
+        }
+        for meta in metas {
+            // SAFETY: Each meta was produced by `blaze_user_meta::from`.
+            let () = unsafe { meta.free() };
+        }
+    }
+
+    /// Parse the blob into `metas`/`outputs`, returning `None` on any malformed
+    /// input. The caller frees whatever was built on failure.
+    fn parse(
+        data: &[u8],
+        metas: &mut Vec<blaze_user_meta>,
+        outputs: &mut Vec<blaze_normalized_output>,
+    ) -> Option<()> {
+        let mut cursor = Cursor::new(data);
+        if cursor.take(BLAZE_NORM_SERIALIZE_MAGIC.len())? != BLAZE_NORM_SERIALIZE_MAGIC {
+            return None
+        }
+        if cursor.u32()? != BLAZE_NORM_SERIALIZE_VERSION {
+            return None
+        }
+
+        let meta_cnt = cursor.u64()? as usize;
+        let () = metas.reserve(meta_cnt);
+        for _ in 0..meta_cnt {
+            let meta = match cursor.u8()? {
+                0 => {
+                    let reason = match cursor.u8()? {
+                        0 => Reason::Unmapped,
+                        1 => Reason::MissingComponent,
+                        2 => Reason::Unsupported,
+                        _ => return None,
+                    };
+                    UserMeta::Unknown(Unknown {
+                        reason,
+                        _non_exhaustive: (),
+                    })
+                }
+                1 => {
+                    let path = PathBuf::from(OsString::from_vec(cursor.bytes()?.to_vec()));
+                    UserMeta::Apk(Apk {
+                        path,
+                        _non_exhaustive: (),
+                    })
+                }
+                2 => {
+                    let path = PathBuf::from(OsString::from_vec(cursor.bytes()?.to_vec()));
+                    let build_id = cursor
+                        .opt_bytes()?
+                        .map(|build_id| Cow::Owned(build_id.to_vec()));
+                    UserMeta::Elf(Elf {
+                        path,
+                        build_id,
+                        _non_exhaustive: (),
+                    })
+                }
+                _ => return None,
+            };
+            let () = metas.push(ManuallyDrop::into_inner(blaze_user_meta::from(meta)));
+        }
+
+        let output_cnt = cursor.u64()? as usize;
+        let () = outputs.reserve(output_cnt);
+        for _ in 0..output_cnt {
+            let output = cursor.u64()?;
+            let meta_idx = cursor.u64()? as usize;
+            // Reject blobs that reference a meta out of bounds: consumers index
+            // `metas.add(meta_idx)` directly, so an unchecked value is an
+            // out-of-bounds read.
+            if meta_idx >= metas.len() {
+                return None
+            }
+            let mut norm = blaze_normalized_output::from((output, meta_idx));
+            if let Some(build_id) = cursor.opt_bytes()? {
+                norm.build_id_len = build_id.len();
+                // SAFETY: We know the pointer is valid because it came from a `Box`.
+                norm.build_id = unsafe {
+                    Box::into_raw(build_id.to_vec().into_boxed_slice())
+                        .as_mut()
+                        .unwrap()
+                        .as_mut_ptr()
+                };
+            }
+            norm.has_reason = cursor.u8()? != 0;
+            norm.reason = reason_from_u8(cursor.u8()?)?;
+            let () = outputs.push(norm);
+        }
+
+        Some(())
+    }
+
+    let mut metas = Vec::new();
+    let mut outputs = Vec::new();
+    if parse(data, &mut metas, &mut outputs).is_none() {
+        let () = free_partial(metas, outputs);
+        return None
+    }
+
+    let slf = blaze_normalized_user_output {
+        meta_cnt: metas.len(),
+        metas: unsafe {
+            Box::into_raw(metas.into_boxed_slice())
+                .as_mut()
+                .unwrap()
+                .as_mut_ptr()
+        },
+        output_cnt: outputs.len(),
+        outputs: unsafe {
+            Box::into_raw(outputs.into_boxed_slice())
+                .as_mut()
+                .unwrap()
+                .as_mut_ptr()
+        },
+        reserved: [0u8; 8],
+    };
+    Some(ManuallyDrop::new(slf))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::CStr;
+    use std::io;
+    use std::path::Path;
+
+    use blazesym::helper::read_elf_build_id;
+
+    use test_tag::tag;
+
+    use crate::blaze_err_last;
+
+
+    /// Check that various types have expected sizes.
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn type_sizes() {
+        assert_eq!(size_of::<blaze_normalizer_opts>(), 16);
+        assert_eq!(size_of::<blaze_normalize_opts>(), 16);
+        assert_eq!(size_of::<blaze_user_meta_apk>(), 16);
+        assert_eq!(size_of::<blaze_user_meta_elf>(), 32);
+        assert_eq!(size_of::<blaze_user_meta_unknown>(), 8);
+        assert_eq!(size_of::<blaze_normalized_output>(), 40);
+        assert_eq!(size_of::<blaze_denormalize_addr>(), 40);
+        assert_eq!(size_of::<blaze_denormalized_output>(), 16);
+    }
+
+    /// Exercise the `Debug` representation of various types.
+    #[tag(miri)]
+    #[test]
+    fn debug_repr() {
+        let output = blaze_normalized_output {
+            output: 0x1337,
+            meta_idx: 1,
+            build_id_len: 0,
+            build_id: ptr::null_mut(),
+            has_reason: false,
+            reason: blaze_normalize_reason::BLAZE_NORMALIZE_REASON_UNMAPPED,
+        };
+        assert_eq!(
+            format!("{output:?}"),
+            "blaze_normalized_output { output: 4919, meta_idx: 1, build_id_len: 0, build_id: 0x0, has_reason: false, reason: BLAZE_NORMALIZE_REASON_UNMAPPED }"
+        );
+
+        let meta_kind = blaze_user_meta_kind::BLAZE_USER_META_APK;
+        assert_eq!(format!("{meta_kind:?}"), "BLAZE_USER_META_APK");
+
+        let apk = blaze_user_meta_apk {
+            path: ptr::null_mut(),
+            reserved: [0u8; 8],
+        };
+        assert_eq!(
+            format!("{apk:?}"),
+            "blaze_user_meta_apk { path: 0x0, reserved: [0, 0, 0, 0, 0, 0, 0, 0] }",
+        );
+
+        let elf = blaze_user_meta_elf {
+            path: ptr::null_mut(),
+            build_id_len: 0,
+            build_id: ptr::null_mut(),
             reserved: [0u8; 8],
         };
         assert_eq!(
@@ -937,6 +2472,257 @@ mod tests {
         let () = unsafe { blaze_normalizer_free(normalizer) };
     }
 
+    /// Check that we can normalize a batch of addresses tagged with PIDs.
+    #[test]
+    fn normalize_user_addrs_multi() {
+        let pids = [0u32];
+        let addrs = [
+            blaze_normalize_addr {
+                addr: libc::__errno_location as Addr,
+                pid_idx: 0,
+            },
+            blaze_normalize_addr {
+                addr: libc::dlopen as Addr,
+                pid_idx: 0,
+            },
+            blaze_normalize_addr {
+                addr: elf_conversion as Addr,
+                pid_idx: 0,
+            },
+            blaze_normalize_addr {
+                addr: normalize_user_addrs as Addr,
+                pid_idx: 0,
+            },
+        ];
+
+        let normalizer = blaze_normalizer_new();
+        assert_ne!(normalizer, ptr::null_mut());
+
+        let opts = blaze_normalize_opts::default();
+        let result = unsafe {
+            blaze_normalize_user_addrs_multi(
+                normalizer,
+                pids.as_slice().as_ptr(),
+                pids.len(),
+                addrs.as_slice().as_ptr(),
+                addrs.len(),
+                &opts,
+            )
+        };
+        assert_ne!(result, ptr::null_mut());
+
+        let user_addrs = unsafe { &*result };
+        assert_eq!(user_addrs.output_cnt, 4);
+
+        let () = unsafe { blaze_user_output_free(result) };
+        let () = unsafe { blaze_normalizer_free(normalizer) };
+    }
+
+    /// Check that an out-of-range `pid_idx` is rejected.
+    #[test]
+    fn normalize_user_addrs_multi_bad_pid_idx() {
+        let pids = [0u32];
+        let addrs = [blaze_normalize_addr {
+            addr: normalize_user_addrs as Addr,
+            pid_idx: 1,
+        }];
+
+        let normalizer = blaze_normalizer_new();
+        assert_ne!(normalizer, ptr::null_mut());
+
+        let opts = blaze_normalize_opts::default();
+        let result = unsafe {
+            blaze_normalize_user_addrs_multi(
+                normalizer,
+                pids.as_slice().as_ptr(),
+                pids.len(),
+                addrs.as_slice().as_ptr(),
+                addrs.len(),
+                &opts,
+            )
+        };
+        assert_eq!(result, ptr::null_mut());
+        assert_eq!(blaze_err_last(), blaze_err::BLAZE_ERR_INVALID_INPUT);
+
+        let () = unsafe { blaze_normalizer_free(normalizer) };
+    }
+
+    /// Check that we can denormalize an address back to its live location,
+    /// round-tripping through normalization.
+    #[test]
+    fn denormalize_user_addrs() {
+        let addr = normalize_user_addrs as Addr;
+
+        let normalizer = blaze_normalizer_new();
+        assert_ne!(normalizer, ptr::null_mut());
+
+        let result = unsafe { blaze_normalize_user_addrs(normalizer, 0, &addr, 1) };
+        assert_ne!(result, ptr::null_mut());
+        let normalized = unsafe { &*result };
+        assert_eq!(normalized.output_cnt, 1);
+
+        let output = unsafe { &*normalized.outputs.add(0) };
+        let meta = unsafe { &*normalized.metas.add(output.meta_idx) };
+        assert_eq!(meta.kind, blaze_user_meta_kind::BLAZE_USER_META_ELF);
+        let elf = unsafe { &meta.variant.elf };
+
+        let input = blaze_denormalize_addr {
+            path: elf.path as *const c_char,
+            file_offset: output.output,
+            build_id_len: 0,
+            build_id: ptr::null(),
+            reserved: [0u8; 8],
+        };
+        let denorm = unsafe { blaze_denormalize_user_addrs(normalizer, 0, &input, 1) };
+        assert_ne!(denorm, ptr::null_mut());
+        let denorm_ref = unsafe { &*denorm };
+        assert_eq!(denorm_ref.output_cnt, 1);
+        let denorm_output = unsafe { &*denorm_ref.outputs.add(0) };
+        assert_eq!(denorm_output.addr, addr);
+
+        let () = unsafe { blaze_denormalize_user_output_free(denorm) };
+        let () = unsafe { blaze_user_output_free(result) };
+        let () = unsafe { blaze_normalizer_free(normalizer) };
+    }
+
+    /// Check that denormalizing an unmapped object reports the expected reason.
+    #[test]
+    fn denormalize_user_addrs_not_mapped() {
+        let path = CString::new("/does/not/exist.so").unwrap();
+        let input = blaze_denormalize_addr {
+            path: path.as_ptr(),
+            file_offset: 0x1000,
+            build_id_len: 0,
+            build_id: ptr::null(),
+            reserved: [0u8; 8],
+        };
+
+        let normalizer = blaze_normalizer_new();
+        assert_ne!(normalizer, ptr::null_mut());
+
+        let denorm = unsafe { blaze_denormalize_user_addrs(normalizer, 0, &input, 1) };
+        assert_ne!(denorm, ptr::null_mut());
+        let denorm_output = unsafe { &*(*denorm).outputs.add(0) };
+        assert_eq!(denorm_output.addr, 0);
+        assert_eq!(
+            denorm_output.reason,
+            blaze_normalize_reason::BLAZE_NORMALIZE_REASON_NOT_MAPPED
+        );
+
+        let () = unsafe { blaze_denormalize_user_output_free(denorm) };
+        let () = unsafe { blaze_normalizer_free(normalizer) };
+    }
+
+    /// Check that we can symbolize an ELF file offset produced by the
+    /// normalizer, closing the normalize -> symbolize loop.
+    #[test]
+    fn symbolize_elf_file_offsets() {
+        let test_so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("data")
+            .join("libtest-so.so")
+            .canonicalize()
+            .unwrap();
+        let so_cstr = CString::new(test_so.clone().into_os_string().into_vec()).unwrap();
+        let handle = unsafe { libc::dlopen(so_cstr.as_ptr(), libc::RTLD_NOW) };
+        assert!(!handle.is_null());
+
+        let the_answer_addr = unsafe { libc::dlsym(handle, "the_answer\0".as_ptr().cast()) };
+        assert!(!the_answer_addr.is_null());
+
+        // Normalize the address to recover its ELF file offset.
+        let normalizer = blaze_normalizer_new();
+        assert_ne!(normalizer, ptr::null_mut());
+        let opts = blaze_normalize_opts {
+            sorted_addrs: true,
+            ..Default::default()
+        };
+        let addrs = [the_answer_addr as Addr];
+        let result = unsafe {
+            blaze_normalize_user_addrs_opts(
+                normalizer,
+                0,
+                addrs.as_slice().as_ptr(),
+                addrs.len(),
+                &opts,
+            )
+        };
+        assert_ne!(result, ptr::null_mut());
+        let normalized = unsafe { &*result };
+        let file_offset = unsafe { (*normalized.outputs.add(0)).output };
+
+        let rc = unsafe { libc::dlclose(handle) };
+        assert_eq!(rc, 0, "{}", io::Error::last_os_error());
+
+        // Feed the file offset back into symbolization.
+        let offsets = [file_offset];
+        let syms = unsafe {
+            blaze_symbolize_elf_file_offsets(
+                so_cstr.as_ptr(),
+                offsets.as_slice().as_ptr(),
+                offsets.len(),
+            )
+        };
+        assert_ne!(syms, ptr::null_mut());
+        let syms_ref = unsafe { &*syms };
+        assert_eq!(syms_ref.cnt, 1);
+
+        let sym = unsafe { &*syms_ref.syms.add(0) };
+        assert!(!sym.name.is_null());
+        let name = unsafe { CStr::from_ptr(sym.name) };
+        assert_eq!(name.to_bytes(), b"the_answer");
+
+        let () = unsafe { blaze_syms_file_offsets_free(syms) };
+        let () = unsafe { blaze_user_output_free(result) };
+        let () = unsafe { blaze_normalizer_free(normalizer) };
+    }
+
+    /// Check that we can normalize against a caller-supplied maps blob offline.
+    #[test]
+    fn normalize_user_addrs_from_maps() {
+        let maps = b"00400000-00401000 r-xp 00001000 fd:00 1234 /tmp/file.so\n";
+        let addrs = [0x400100 as Addr, 0x5000 as Addr];
+
+        let normalizer = blaze_normalizer_new();
+        assert_ne!(normalizer, ptr::null_mut());
+
+        let opts = blaze_normalize_opts::default();
+        let result = unsafe {
+            blaze_normalize_user_addrs_from_maps(
+                normalizer,
+                maps.as_ptr(),
+                maps.len(),
+                addrs.as_slice().as_ptr(),
+                addrs.len(),
+                &opts,
+            )
+        };
+        assert_ne!(result, ptr::null_mut());
+
+        let normalized = unsafe { &*result };
+        assert_eq!(normalized.output_cnt, 2);
+
+        // First address falls inside the mapping.
+        let output = unsafe { &*normalized.outputs.add(0) };
+        assert_eq!(output.output, 0x1100);
+        let meta = unsafe { &*normalized.metas.add(output.meta_idx) };
+        assert_eq!(meta.kind, blaze_user_meta_kind::BLAZE_USER_META_ELF);
+        let path = unsafe { CStr::from_ptr(meta.variant.elf.path) };
+        assert_eq!(path.to_bytes(), b"/tmp/file.so");
+
+        // Second address is not mapped at all.
+        let output = unsafe { &*normalized.outputs.add(1) };
+        let meta = unsafe { &*normalized.metas.add(output.meta_idx) };
+        assert_eq!(meta.kind, blaze_user_meta_kind::BLAZE_USER_META_UNKNOWN);
+        assert_eq!(
+            unsafe { meta.variant.unknown.reason },
+            blaze_normalize_reason::BLAZE_NORMALIZE_REASON_UNMAPPED
+        );
+
+        let () = unsafe { blaze_user_output_free(result) };
+        let () = unsafe { blaze_normalizer_free(normalizer) };
+    }
+
     /// Check that we can normalize sorted user space addresses.
     #[test]
     fn normalize_user_addrs_sorted() {
@@ -1083,4 +2869,272 @@ mod tests {
         test(true);
         test(false);
     }
+
+    /// Check that a [`blaze_normalized_user_output`] survives a
+    /// serialize/deserialize round-trip unchanged.
+    #[tag(miri)]
+    #[test]
+    fn serialize_round_trip() {
+        fn metas_ptr(metas: Vec<blaze_user_meta>) -> *mut blaze_user_meta {
+            unsafe {
+                Box::into_raw(metas.into_boxed_slice())
+                    .as_mut()
+                    .unwrap()
+                    .as_mut_ptr()
+            }
+        }
+
+        fn outputs_ptr(outputs: Vec<blaze_normalized_output>) -> *mut blaze_normalized_output {
+            unsafe {
+                Box::into_raw(outputs.into_boxed_slice())
+                    .as_mut()
+                    .unwrap()
+                    .as_mut_ptr()
+            }
+        }
+
+        let metas = vec![
+            ManuallyDrop::into_inner(blaze_user_meta::from(UserMeta::Elf(Elf {
+                path: PathBuf::from("/tmp/file.so"),
+                build_id: Some(Cow::Borrowed(&[0x01, 0x02, 0x03, 0x04])),
+                _non_exhaustive: (),
+            }))),
+            ManuallyDrop::into_inner(blaze_user_meta::from(UserMeta::Unknown(Unknown {
+                reason: Reason::Unmapped,
+                _non_exhaustive: (),
+            }))),
+        ];
+        let outputs = vec![
+            blaze_normalized_output::from((0x1000, 0)),
+            blaze_normalized_output::from((0x2000, 1)),
+        ];
+        let output = Box::into_raw(Box::new(blaze_normalized_user_output {
+            meta_cnt: 2,
+            metas: metas_ptr(metas),
+            output_cnt: 2,
+            outputs: outputs_ptr(outputs),
+            reserved: [0u8; 8],
+        }));
+
+        let buffer = unsafe { blaze_normalized_user_output_serialize(output) };
+        assert!(!buffer.is_null());
+        let buf = unsafe { &*buffer };
+        let data = unsafe { slice_from_user_array(buf.data, buf.len) };
+
+        let restored = unsafe {
+            blaze_normalized_user_output_deserialize(data.as_ptr(), data.len())
+        };
+        assert!(!restored.is_null());
+        let restored_ref = unsafe { &*restored };
+        assert_eq!(restored_ref.meta_cnt, 2);
+        assert_eq!(restored_ref.output_cnt, 2);
+
+        let elf = unsafe { &(*restored_ref.metas.add(0)).variant.elf };
+        let path = unsafe { CStr::from_ptr(elf.path) };
+        assert_eq!(path.to_bytes(), b"/tmp/file.so");
+        let build_id = unsafe { slice_from_user_array(elf.build_id, elf.build_id_len) };
+        assert_eq!(&*build_id, &[0x01, 0x02, 0x03, 0x04]);
+
+        let out0 = unsafe { &*restored_ref.outputs.add(0) };
+        assert_eq!(out0.output, 0x1000);
+        assert_eq!(out0.meta_idx, 0);
+
+        let () = unsafe { blaze_normalized_output_buffer_free(buffer) };
+        let () = unsafe { blaze_user_output_free(restored) };
+        let () = unsafe { blaze_user_output_free(output) };
+    }
+
+    /// Check that deserialization rejects a blob with a bad magic.
+    #[tag(miri)]
+    #[test]
+    fn deserialize_bad_magic() {
+        let data = *b"NOTMAGIC";
+        let restored =
+            unsafe { blaze_normalized_user_output_deserialize(data.as_ptr(), data.len()) };
+        assert!(restored.is_null());
+        assert_eq!(blaze_err_last(), blaze_err::BLAZE_ERR_INVALID_INPUT);
+    }
+
+    /// Check that `emit_build_id` populates the per-output build ID.
+    #[test]
+    fn normalize_emit_build_id() {
+        let test_so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("data")
+            .join("libtest-so.so")
+            .canonicalize()
+            .unwrap();
+        let so_cstr = CString::new(test_so.clone().into_os_string().into_vec()).unwrap();
+        let handle = unsafe { libc::dlopen(so_cstr.as_ptr(), libc::RTLD_NOW) };
+        assert!(!handle.is_null());
+
+        let the_answer_addr = unsafe { libc::dlsym(handle, "the_answer\0".as_ptr().cast()) };
+        assert!(!the_answer_addr.is_null());
+
+        let opts = blaze_normalizer_opts {
+            build_ids: true,
+            ..Default::default()
+        };
+        let normalizer = unsafe { blaze_normalizer_new_opts(&opts) };
+        assert!(!normalizer.is_null());
+
+        let opts = blaze_normalize_opts {
+            sorted_addrs: true,
+            emit_build_id: true,
+            ..Default::default()
+        };
+        let addrs = [the_answer_addr as Addr];
+        let result = unsafe {
+            blaze_normalize_user_addrs_opts(
+                normalizer,
+                0,
+                addrs.as_slice().as_ptr(),
+                addrs.len(),
+                &opts,
+            )
+        };
+        assert!(!result.is_null());
+
+        let normalized = unsafe { &*result };
+        assert_eq!(normalized.output_cnt, 1);
+
+        let rc = unsafe { libc::dlclose(handle) };
+        assert_eq!(rc, 0, "{}", io::Error::last_os_error());
+
+        let output = unsafe { &*normalized.outputs.add(0) };
+        let expected = read_elf_build_id(&test_so).unwrap().unwrap();
+        assert!(!output.build_id.is_null());
+        let build_id = unsafe { slice_from_user_array(output.build_id, output.build_id_len) };
+        assert_eq!(build_id, expected.as_ref());
+
+        let () = unsafe { blaze_user_output_free(result) };
+        let () = unsafe { blaze_normalizer_free(normalizer) };
+    }
+
+    /// Check that streaming normalization invokes the callback once per
+    /// resolved address.
+    #[test]
+    fn normalize_user_addrs_stream() {
+        struct StreamState {
+            count: usize,
+        }
+
+        unsafe extern "C" fn cb(
+            _idx: usize,
+            output: *const blaze_normalized_output,
+            meta: *const blaze_user_meta,
+            user_data: *mut c_void,
+        ) {
+            assert!(!output.is_null());
+            assert!(!meta.is_null());
+            let state = unsafe { &mut *(user_data as *mut StreamState) };
+            state.count += 1;
+        }
+
+        let addrs = [
+            libc::__errno_location as Addr,
+            libc::fopen as Addr,
+            normalize_user_addrs as Addr,
+        ];
+
+        let normalizer = blaze_normalizer_new();
+        assert_ne!(normalizer, ptr::null_mut());
+
+        let mut state = StreamState { count: 0 };
+        let opts = blaze_normalize_opts::default();
+        let ok = unsafe {
+            blaze_normalize_user_addrs_stream(
+                normalizer,
+                0,
+                addrs.as_slice().as_ptr(),
+                addrs.len(),
+                &opts,
+                Some(cb),
+                &mut state as *mut _ as *mut c_void,
+            )
+        };
+        assert!(ok);
+        assert_eq!(state.count, addrs.len());
+
+        let () = unsafe { blaze_normalizer_free(normalizer) };
+    }
+
+    /// Check that a `NULL` callback is rejected.
+    #[test]
+    fn normalize_user_addrs_stream_null_cb() {
+        let addrs = [normalize_user_addrs as Addr];
+        let normalizer = blaze_normalizer_new();
+        assert_ne!(normalizer, ptr::null_mut());
+
+        let opts = blaze_normalize_opts::default();
+        let ok = unsafe {
+            blaze_normalize_user_addrs_stream(
+                normalizer,
+                0,
+                addrs.as_slice().as_ptr(),
+                addrs.len(),
+                &opts,
+                None,
+                ptr::null_mut(),
+            )
+        };
+        assert!(!ok);
+        assert_eq!(blaze_err_last(), blaze_err::BLAZE_ERR_INVALID_INPUT);
+
+        let () = unsafe { blaze_normalizer_free(normalizer) };
+    }
+
+    /// Check that `report_reasons` does *not* flag a caveat merely because
+    /// build-ID reading was left disabled: the object here carries a build ID,
+    /// so nothing is actually missing.
+    #[test]
+    fn normalize_report_reasons() {
+        let test_so = Path::new(&env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("data")
+            .join("libtest-so.so")
+            .canonicalize()
+            .unwrap();
+        let so_cstr = CString::new(test_so.clone().into_os_string().into_vec()).unwrap();
+        let handle = unsafe { libc::dlopen(so_cstr.as_ptr(), libc::RTLD_NOW) };
+        assert!(!handle.is_null());
+
+        let the_answer_addr = unsafe { libc::dlsym(handle, "the_answer\0".as_ptr().cast()) };
+        assert!(!the_answer_addr.is_null());
+
+        // Build ID reading is left disabled on the normalizer on purpose.
+        let normalizer = blaze_normalizer_new();
+        assert_ne!(normalizer, ptr::null_mut());
+
+        let opts = blaze_normalize_opts {
+            sorted_addrs: true,
+            report_reasons: true,
+            ..Default::default()
+        };
+        let addrs = [the_answer_addr as Addr];
+        let result = unsafe {
+            blaze_normalize_user_addrs_opts(
+                normalizer,
+                0,
+                addrs.as_slice().as_ptr(),
+                addrs.len(),
+                &opts,
+            )
+        };
+        assert_ne!(result, ptr::null_mut());
+
+        let rc = unsafe { libc::dlclose(handle) };
+        assert_eq!(rc, 0, "{}", io::Error::last_os_error());
+
+        let normalized = unsafe { &*result };
+        let output = unsafe { &*normalized.outputs.add(0) };
+        let meta = unsafe { &*normalized.metas.add(output.meta_idx) };
+        assert_eq!(meta.kind, blaze_user_meta_kind::BLAZE_USER_META_ELF);
+        // The object has a readable build ID, so no caveat should be reported
+        // even though the normalizer did not read (and hence expose) it.
+        assert!(!output.has_reason);
+
+        let () = unsafe { blaze_user_output_free(result) };
+        let () = unsafe { blaze_normalizer_free(normalizer) };
+    }
 }